@@ -1,20 +1,106 @@
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::thread;
-use std::time::Duration;
 
-fn main() {
-    thread::spawn(|| {
-        for i in 1..100 {
-            println!("T1: {}", i);
-            thread::sleep(Duration::from_millis(1));
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+enum Message {
+    NewJob(Job),
+    Terminate,
+}
+
+pub struct ThreadPool {
+    workers: Vec<Worker>,
+    sender: mpsc::Sender<Message>,
+}
+
+impl ThreadPool {
+    /// Creates a new ThreadPool.
+    ///
+    /// The size is the number of threads in the pool.
+    ///
+    /// # Panics
+    ///
+    /// `new` panics if size is zero.
+    pub fn new(size: usize) -> ThreadPool {
+        assert!(size > 0);
+
+        let (sender, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let mut workers = Vec::with_capacity(size);
+        for id in 0..size {
+            workers.push(Worker::new(id, Arc::clone(&receiver)));
         }
-    });
-    thread::spawn(|| {
-        for i in 1..100 {
-            println!("T2: {}", i);
-            thread::sleep(Duration::from_millis(1));
+
+        ThreadPool { workers, sender }
+    }
+
+    pub fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let job = Box::new(f);
+
+        self.sender.send(Message::NewJob(job)).unwrap();
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        for _ in &self.workers {
+            self.sender.send(Message::Terminate).unwrap();
         }
-    });
 
-    // Wait 1 second, for the other threads to finish
-    thread::sleep(Duration::from_millis(1000));
+        for worker in &mut self.workers {
+            if let Some(thread) = worker.thread.take() {
+                thread.join().unwrap();
+            }
+        }
+    }
+}
+
+struct Worker {
+    id: usize,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Worker {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Message>>>) -> Worker {
+        let thread = thread::spawn(move || loop {
+            let message = receiver.lock().unwrap().recv().unwrap();
+
+            match message {
+                Message::NewJob(job) => job(),
+                Message::Terminate => break,
+            }
+        });
+
+        Worker {
+            id,
+            thread: Some(thread),
+        }
+    }
+}
+
+fn main() {
+    let pool = ThreadPool::new(4);
+
+    let (sender, receiver) = mpsc::channel();
+
+    for i in 0..100 {
+        let sender = sender.clone();
+        pool.execute(move || {
+            println!("job {}", i);
+            sender.send(()).unwrap();
+        });
+    }
+    drop(sender);
+
+    // Block until all 100 jobs have reported completion, so main exits
+    // deterministically instead of racing the worker threads.
+    for _ in 0..100 {
+        receiver.recv().unwrap();
+    }
 }
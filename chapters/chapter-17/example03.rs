@@ -22,8 +22,14 @@ impl Post {
 	}
     }
 
+    pub fn reject(&mut self) {
+	if let Some(s) = self.state.take() {
+	    self.state = Some(s.reject())
+	}
+    }
+
     pub fn content(&self) -> &str {
-	&self.content
+	self.state.as_ref().unwrap().content(self)
     }
 
     pub fn request_review(&mut self) {
@@ -38,19 +44,30 @@ struct Draft {}
 trait State {
     fn request_review(self: Box<Self>) -> Box<dyn State>;
     fn approve(self: Box<Self>) -> Box<dyn State>;
+    fn reject(self: Box<Self>) -> Box<dyn State>;
+
+    fn content<'a>(&self, _post: &'a Post) -> &'a str {
+	""
+    }
 }
 
 impl State for Draft {
     fn request_review(self: Box<Self>) -> Box<dyn State> {
-	self
+	Box::new(PendingReview { approvals: 0 })
     }
 
     fn approve(self: Box<Self>) -> Box<dyn State> {
 	self
     }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+	self
+    }
 }
 
-struct PendingReview {}
+struct PendingReview {
+    approvals: u8,
+}
 
 impl State for PendingReview {
     fn request_review(self: Box<Self>) -> Box<dyn State> {
@@ -58,7 +75,17 @@ impl State for PendingReview {
     }
 
     fn approve(self: Box<Self>) -> Box<dyn State> {
-	Box::new(Published {})
+	if self.approvals + 1 >= 2 {
+	    Box::new(Published {})
+	} else {
+	    Box::new(PendingReview {
+		approvals: self.approvals + 1,
+	    })
+	}
+    }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+	Box::new(Draft {})
     }
 }
 
@@ -72,6 +99,14 @@ impl State for Published {
     fn approve(self: Box<Self>) -> Box<dyn State> {
 	self
     }
+
+    fn reject(self: Box<Self>) -> Box<dyn State> {
+	self
+    }
+
+    fn content<'a>(&self, post: &'a Post) -> &'a str {
+	&post.content
+    }
 }
 
 fn main() {
@@ -83,6 +118,22 @@ fn main() {
     post.request_review();
     assert_eq!("", post.content());
 
+    post.approve();
+    assert_eq!("", post.content());
+
     post.approve();
     assert_eq!("I ate a salad for lunch today", post.content());
+
+    // Rejecting sends a pending review back to draft, and it needs a
+    // fresh request_review + two approvals to publish again.
+    let mut rejected = Post::new();
+    rejected.add_text("a draft that gets sent back");
+    rejected.request_review();
+    rejected.reject();
+    assert_eq!("", rejected.content());
+
+    rejected.request_review();
+    rejected.approve();
+    rejected.approve();
+    assert_eq!("a draft that gets sent back", rejected.content());
 }
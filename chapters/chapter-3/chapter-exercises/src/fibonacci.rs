@@ -8,20 +8,108 @@ fn main() {
         .read_line(&mut n)
         .expect("Failed to read your input");
 
-    let result : i32 = match n.trim().parse() {
+    let result = match n.trim().parse() {
         Ok(num) => fibonacci(num),
         Err(_) => panic!("Foo"),
     };
 
-    println!("The {}th fibonacci number is {}", n.trim(), result);
+    match result {
+        Ok(value) => println!("The {}th fibonacci number is {}", n.trim(), value),
+        Err(e) => println!("Could not compute fibonacci({}): {}", n.trim(), e),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum FibonacciError {
+    Negative,
+    Overflow,
+}
+
+impl std::fmt::Display for FibonacciError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            FibonacciError::Negative => write!(f, "n must not be negative"),
+            FibonacciError::Overflow => write!(f, "result overflows u128"),
+        }
+    }
+}
+
+/// Computes the nth Fibonacci number in O(log n) using fast doubling:
+/// given (F(k), F(k+1)) for k = n >> 1, F(2k) = F(k) * (2*F(k+1) - F(k))
+/// and F(2k+1) = F(k)^2 + F(k+1)^2. Uses checked arithmetic so overflow
+/// is reported rather than silently wrapping.
+fn fibonacci(n: i32) -> Result<u128, FibonacciError> {
+    if n < 0 {
+        return Err(FibonacciError::Negative);
+    }
+
+    let n = n as u64;
+    if n == 0 {
+        return Ok(0);
+    }
+
+    // Finish the last doubling step for F(n) alone: the full pair would also
+    // need F(n+1), which overflows first (F(186) fits in u128, F(187) does not).
+    let (a, b) = fast_doubling(n >> 1)?;
+    let value = if n & 1 == 0 {
+        b.checked_mul(2)
+            .and_then(|two_b| two_b.checked_sub(a))
+            .and_then(|two_b_minus_a| a.checked_mul(two_b_minus_a))
+    } else {
+        a.checked_mul(a).and_then(|aa| b.checked_mul(b).and_then(|bb| aa.checked_add(bb)))
+    };
+    value.ok_or(FibonacciError::Overflow)
 }
 
-fn fibonacci(n: i32) -> i32 {
-    match n {
-        0 => 0,
-        1 => 1,
-        2 => 1,
-        3 => 2,
-        n => fibonacci(n-1) + fibonacci(n-2)
+fn fast_doubling(n: u64) -> Result<(u128, u128), FibonacciError> {
+    if n == 0 {
+        return Ok((0, 1));
+    }
+
+    let (a, b) = fast_doubling(n >> 1)?;
+
+    let two_b_minus_a = b
+        .checked_mul(2)
+        .and_then(|two_b| two_b.checked_sub(a))
+        .ok_or(FibonacciError::Overflow)?;
+    let c = a.checked_mul(two_b_minus_a).ok_or(FibonacciError::Overflow)?;
+    let d = a
+        .checked_mul(a)
+        .and_then(|aa| b.checked_mul(b).and_then(|bb| aa.checked_add(bb)))
+        .ok_or(FibonacciError::Overflow)?;
+
+    if n & 1 == 0 {
+        Ok((c, d))
+    } else {
+        let next = c.checked_add(d).ok_or(FibonacciError::Overflow)?;
+        Ok((d, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_values() {
+        assert_eq!(fibonacci(0), Ok(0));
+        assert_eq!(fibonacci(1), Ok(1));
+        assert_eq!(fibonacci(2), Ok(1));
+        assert_eq!(fibonacci(10), Ok(55));
+        assert_eq!(
+            fibonacci(100),
+            Ok(354224848179261915075)
+        );
+    }
+
+    #[test]
+    fn rejects_negative_input() {
+        assert_eq!(fibonacci(-1), Err(FibonacciError::Negative));
+    }
+
+    #[test]
+    fn reports_overflow_instead_of_wrapping() {
+        // F(187) is the first Fibonacci number above u128::MAX; this is well past that.
+        assert_eq!(fibonacci(i32::MAX), Err(FibonacciError::Overflow));
     }
 }
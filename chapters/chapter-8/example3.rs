@@ -1,7 +1,189 @@
+use std::collections::HashSet;
+use std::fmt;
+
 enum SpreadsheetCell {
     Int(i32),
     Float(f64),
     Text(String),
+    Formula(String),
+}
+
+#[derive(Debug)]
+enum EvalError {
+    OutOfBounds(usize),
+    NotANumber(usize),
+    Cycle(usize),
+    UnexpectedEnd,
+    UnexpectedToken(String),
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::OutOfBounds(i) => write!(f, "cell {} does not exist", i),
+            EvalError::NotANumber(i) => write!(f, "cell {} is text, not a number", i),
+            EvalError::Cycle(i) => write!(f, "cell {} is part of a reference cycle", i),
+            EvalError::UnexpectedEnd => write!(f, "unexpected end of formula"),
+            EvalError::UnexpectedToken(t) => write!(f, "unexpected token: {}", t),
+        }
+    }
+}
+
+struct Sheet {
+    cells: Vec<SpreadsheetCell>,
+}
+
+impl Sheet {
+    fn new(cells: Vec<SpreadsheetCell>) -> Sheet {
+        Sheet { cells }
+    }
+
+    fn eval(&self, index: usize) -> Result<f64, EvalError> {
+        let mut visiting = HashSet::new();
+        self.eval_cell(index, &mut visiting)
+    }
+
+    fn eval_cell(&self, index: usize, visiting: &mut HashSet<usize>) -> Result<f64, EvalError> {
+        let cell = self.cells.get(index).ok_or(EvalError::OutOfBounds(index))?;
+
+        match cell {
+            SpreadsheetCell::Int(i) => Ok(*i as f64),
+            SpreadsheetCell::Float(f) => Ok(*f),
+            SpreadsheetCell::Text(_) => Err(EvalError::NotANumber(index)),
+            SpreadsheetCell::Formula(formula) => {
+                if !visiting.insert(index) {
+                    return Err(EvalError::Cycle(index));
+                }
+
+                let result = Parser::new(formula, self, visiting).parse_expr();
+
+                visiting.remove(&index);
+                result
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<String>,
+    pos: usize,
+    sheet: &'a Sheet,
+    visiting: &'a mut HashSet<usize>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(formula: &str, sheet: &'a Sheet, visiting: &'a mut HashSet<usize>) -> Parser<'a> {
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        Parser {
+            tokens: tokenize(formula),
+            pos: 0,
+            sheet,
+            visiting,
+        }
+    }
+
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|s| s.as_str())
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn parse_expr(&mut self) -> Result<f64, EvalError> {
+        let mut value = self.parse_term()?;
+
+        while let Some(op) = self.peek() {
+            match op {
+                "+" | "-" => {
+                    let op = self.next().unwrap();
+                    let rhs = self.parse_term()?;
+                    value = if op == "+" { value + rhs } else { value - rhs };
+                }
+                _ => break,
+            }
+        }
+
+        Ok(value)
+    }
+
+    // term := factor (('*' | '/') factor)*
+    fn parse_term(&mut self) -> Result<f64, EvalError> {
+        let mut value = self.parse_factor()?;
+
+        while let Some(op) = self.peek() {
+            match op {
+                "*" | "/" => {
+                    let op = self.next().unwrap();
+                    let rhs = self.parse_factor()?;
+                    value = if op == "*" { value * rhs } else { value / rhs };
+                }
+                _ => break,
+            }
+        }
+
+        Ok(value)
+    }
+
+    // factor := number | cell-ref | '(' expr ')'
+    fn parse_factor(&mut self) -> Result<f64, EvalError> {
+        let token = self.next().ok_or(EvalError::UnexpectedEnd)?;
+
+        if token == "(" {
+            let value = self.parse_expr()?;
+            match self.next() {
+                Some(ref t) if t == ")" => Ok(value),
+                _ => Err(EvalError::UnexpectedToken(token)),
+            }
+        } else if let Some(index) = parse_cell_ref(&token) {
+            self.sheet.eval_cell(index, self.visiting)
+        } else if let Ok(number) = token.parse() {
+            Ok(number)
+        } else {
+            Err(EvalError::UnexpectedToken(token))
+        }
+    }
+}
+
+/// Parses references like "A0" into a row index, assuming a single
+/// lettered column (as used by the `Vec<SpreadsheetCell>` row above).
+fn parse_cell_ref(token: &str) -> Option<usize> {
+    let mut chars = token.chars();
+    let column = chars.next()?;
+    if !column.is_ascii_alphabetic() {
+        return None;
+    }
+
+    chars.as_str().parse().ok()
+}
+
+fn tokenize(formula: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut chars = formula.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if "+-*/()".contains(c) {
+            tokens.push(c.to_string());
+            chars.next();
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || "+-*/()".contains(c) {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    tokens
 }
 
 fn main() {
@@ -10,16 +192,23 @@ fn main() {
         SpreadsheetCell::Text(String::from("blue")),
         SpreadsheetCell::Float(10.12),
         SpreadsheetCell::Int(7),
-        SpreadsheetCell::Float(21.04),
+        SpreadsheetCell::Formula(String::from("=A0 + A3 * 2")),
         SpreadsheetCell::Text(String::from("yellow")),
         SpreadsheetCell::Float(29.01),
     ];
 
-    for r in row {
+    for r in &row {
         match r {
             SpreadsheetCell::Int(i) => println!("This is an Int cell, with value: {}", i),
             SpreadsheetCell::Float(f) => println!("This is a Float cell, with value: {}", f),
             SpreadsheetCell::Text(t) => println!("This is an Text cell, with value: {}", t),
+            SpreadsheetCell::Formula(expr) => println!("This is a Formula cell, with expression: {}", expr),
         }
     }
+
+    let sheet = Sheet::new(row);
+    match sheet.eval(4) {
+        Ok(value) => println!("A4 evaluates to {}", value),
+        Err(e) => println!("A4 failed to evaluate: {}", e),
+    }
 }
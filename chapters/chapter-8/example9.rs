@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::io;
 
 struct QuitMessage;
 struct MoveMessage {
@@ -15,6 +17,27 @@ enum Message {
     ChangeColor(i32, i32, i32),
 }
 
+#[derive(Debug)]
+enum ParseError {
+    UnknownCommand(String),
+    WrongArgCount { command: String, expected: usize, got: usize },
+    InvalidInt(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::UnknownCommand(cmd) => write!(f, "unknown command: {}", cmd),
+            ParseError::WrongArgCount { command, expected, got } => write!(
+                f,
+                "{} expects {} argument(s), got {}",
+                command, expected, got
+            ),
+            ParseError::InvalidInt(value) => write!(f, "not a valid integer: {}", value),
+        }
+    }
+}
+
 impl Message {
     fn call(&self) {
         match self {
@@ -26,27 +49,77 @@ impl Message {
             }
         }
     }
+
+    fn parse(line: &str) -> Result<Message, ParseError> {
+        let mut tokens = line.split_whitespace();
+
+        let command = tokens.next().ok_or_else(|| ParseError::UnknownCommand(String::new()))?;
+        let args: Vec<&str> = tokens.collect();
+
+        match command {
+            "quit" => Ok(Message::Quit),
+            "move" => {
+                let nums = parse_ints(command, &args, 2)?;
+                Ok(Message::Move { x: nums[0], y: nums[1] })
+            }
+            "write" => Ok(Message::Write(args.join(" "))),
+            "color" => {
+                let nums = parse_ints(command, &args, 3)?;
+                Ok(Message::ChangeColor(nums[0], nums[1], nums[2]))
+            }
+            other => Err(ParseError::UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+fn parse_ints(command: &str, args: &[&str], expected: usize) -> Result<Vec<i32>, ParseError> {
+    if args.len() != expected {
+        return Err(ParseError::WrongArgCount {
+            command: command.to_string(),
+            expected,
+            got: args.len(),
+        });
+    }
+
+    args.iter()
+        .map(|a| a.parse().map_err(|_| ParseError::InvalidInt(a.to_string())))
+        .collect()
 }
 
 fn main() {
     let mut index = HashMap::new();
 
-    index.insert("first", Message::Move { x: 10, y: 20 });
-    index.insert("second", Message::Write(String::from("marcelo")));
-    index.insert("third", Message::ChangeColor(10, 20, 30));
-    index.insert("fourth", Message::Quit);
+    println!("Message REPL");
+    println!("Commands: move <x> <y> | write <text...> | color <r> <g> <b> | quit");
 
-    match index.get("first") {
-        Some(m) => m.call(),
-        None => println!("Nothing"),
-    }
-    index.insert("first", Message::Write(String::from("marcelo"))); //overrides first
+    loop {
+        let mut line = String::new();
+        io::stdin()
+            .read_line(&mut line)
+            .expect("Failed to read your input");
 
-    scores.entry("second").or_insert(Message::Write(String::from("marcelo"))); // writes if non-existent
-    scores.entry("fifth").or_insert(Message::Write(String::from("marcelo"))); // write since non-existent
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match Message::parse(line) {
+            Ok(Message::Quit) => {
+                index.insert(line.to_string(), Message::Quit);
+                println!("bye");
+                break;
+            }
+            Ok(message) => {
+                message.call();
+                index.insert(line.to_string(), message);
+            }
+            Err(e) => println!("error: {}", e),
+        }
+    }
 
     println!("All messages:");
     for (k, v) in &index {
+        print!("{}: ", k);
         v.call()
     }
 }